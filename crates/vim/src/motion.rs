@@ -0,0 +1,104 @@
+use editor::{
+    display_map::DisplaySnapshot, movement, CharKind, DisplayPoint, SelectionGoal,
+};
+use gpui::{impl_actions, MutableAppContext, ViewContext};
+use serde::Deserialize;
+use workspace::Workspace;
+
+use crate::Vim;
+
+/// A vim motion: something that moves a cursor, independent of whatever selection/operator is
+/// layered on top of it by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    StartOfLine,
+    EndOfLine,
+    NextWordStart,
+    PreviousWordStart,
+}
+
+impl Motion {
+    /// Applies this motion `times` times in a row (a pending count, e.g. the `2` in `2j`/`v2w`),
+    /// returning the resulting point and the selection goal that should replace `goal` (vertical
+    /// motions use this to remember a column past a shorter line in between; the rest reset it).
+    pub fn move_point(
+        self,
+        map: &DisplaySnapshot,
+        point: DisplayPoint,
+        goal: SelectionGoal,
+        times: usize,
+    ) -> (DisplayPoint, SelectionGoal) {
+        let mut point = point;
+        let mut goal = goal;
+        for _ in 0..times.max(1) {
+            let (new_point, new_goal) = self.move_once(map, point, goal);
+            point = new_point;
+            goal = new_goal;
+        }
+        (point, goal)
+    }
+
+    fn move_once(
+        self,
+        map: &DisplaySnapshot,
+        point: DisplayPoint,
+        goal: SelectionGoal,
+    ) -> (DisplayPoint, SelectionGoal) {
+        match self {
+            Motion::Left => (movement::left(map, point), SelectionGoal::None),
+            Motion::Right => (movement::right(map, point), SelectionGoal::None),
+            Motion::Up => movement::up(map, point, goal, false),
+            Motion::Down => movement::down(map, point, goal, false),
+            Motion::StartOfLine => (
+                map.prev_line_boundary(point.to_point(map)).1,
+                SelectionGoal::None,
+            ),
+            Motion::EndOfLine => (
+                map.next_line_boundary(point.to_point(map)).1,
+                SelectionGoal::None,
+            ),
+            Motion::NextWordStart => {
+                let classifier = map.buffer_snapshot.char_classifier_at(point.to_point(map));
+                let next = movement::find_boundary_in_line(map, point, |left, right| {
+                    classifier.kind(left) != classifier.kind(right)
+                        && classifier.kind(right) != CharKind::Whitespace
+                });
+                (next, SelectionGoal::None)
+            }
+            Motion::PreviousWordStart => {
+                let classifier = map.buffer_snapshot.char_classifier_at(point.to_point(map));
+                let previous =
+                    movement::find_preceding_boundary_in_line(map, point, |left, right| {
+                        classifier.kind(left) != classifier.kind(right)
+                            && classifier.kind(left) != CharKind::Whitespace
+                    });
+                (previous, SelectionGoal::None)
+            }
+        }
+    }
+}
+
+/// A single digit keystroke (`1`-`9`, or `0` once a count is already pending) that feeds the
+/// count accumulator used by visual motions and operators (`3j`, `v2w`, `d2w`, ...).
+#[derive(Clone, Default, Deserialize, PartialEq)]
+pub struct Number(pub u8);
+
+impl_actions!(vim, [Number]);
+
+fn number(_: &mut Workspace, &Number(digit): &Number, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, _| {
+        if digit == 0 && vim.state().pending_count.is_none() {
+            // A leading `0` is the "start of line" motion, not the start of a count.
+            return;
+        }
+        vim.push_count_digit(digit);
+    });
+}
+
+pub fn init(cx: &mut MutableAppContext) {
+    cx.add_action(number);
+}
@@ -0,0 +1,69 @@
+use editor::Autoscroll;
+use gpui::{actions, MutableAppContext, ViewContext};
+use workspace::Workspace;
+
+use crate::{
+    object::TextObject,
+    state::{Mode, Operator},
+    utils::copy_selections_content,
+    visual, Vim,
+};
+
+actions!(vim, [PushDelete, PushChange]);
+
+/// `d`/`c` in Normal mode don't act immediately: they arm a pending operator that the next text
+/// object (or motion, handled elsewhere) consumes — `dap`, `ci(`, etc.
+fn push_delete(_: &mut Workspace, _: &PushDelete, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, _| vim.set_pending_operator(Some(Operator::Delete)));
+}
+
+fn push_change(_: &mut Workspace, _: &PushChange, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, _| vim.set_pending_operator(Some(Operator::Change)));
+}
+
+/// Resolves `i`/`a` + a text object (`iw`, `i(`, `at`, ...) against whatever mode we're in: in a
+/// visual mode it replaces the selection with the object's range; in Normal mode with a pending
+/// operator (`d`/`c`) it deletes (or deletes and enters Insert on) the object's range directly,
+/// the same way `dap`/`ci(` work in real vim. A bare `iw`/`i(` with no pending operator and not in
+/// a visual mode is a no-op, same as in vim.
+pub fn object(object: TextObject, around: bool, cx: &mut MutableAppContext) {
+    Vim::update(cx, |vim, cx| {
+        match vim.state().mode {
+            Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
+                visual::expand_selection_to_object(vim, cx, object, around);
+                return;
+            }
+            Mode::Insert => return,
+            Mode::Normal => {}
+        }
+
+        let Some(operator) = vim.pending_operator() else {
+            return;
+        };
+        vim.set_pending_operator(None);
+        let register = vim.selected_register.take();
+
+        vim.update_active_editor(cx, |editor, cx| {
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    if let Some(range) = object.expand_selection(map, selection.head(), around) {
+                        selection.start = range.start;
+                        selection.end = range.end;
+                    }
+                });
+            });
+            copy_selections_content(editor, false, false, register, cx);
+            editor.insert("", cx);
+        });
+
+        match operator {
+            Operator::Delete => vim.switch_mode(Mode::Normal, cx),
+            Operator::Change => vim.switch_mode(Mode::Insert, cx),
+        }
+    });
+}
+
+pub fn init(cx: &mut MutableAppContext) {
+    cx.add_action(push_delete);
+    cx.add_action(push_change);
+}
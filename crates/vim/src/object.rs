@@ -0,0 +1,339 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use editor::{display_map::DisplaySnapshot, movement, CharKind, DisplayPoint};
+
+/// A vim text object: something `iw`/`aw`, `i(`, `a"`, `it`, etc. can select.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextObject {
+    Word,
+    BigWord,
+    Sentence,
+    Paragraph,
+    Parentheses,
+    SquareBrackets,
+    CurlyBrackets,
+    AngleBrackets,
+    Quotes,
+    BackQuotes,
+    DoubleQuotes,
+    Tag,
+}
+
+impl TextObject {
+    /// Whether this object is one of the *distinct*-delimiter kinds, which share a
+    /// balance-nesting-outward scan. The quote variants use `open == close`, which that scan
+    /// can't handle (see `surrounding_quote`), so they're deliberately excluded here.
+    fn delimiters(self) -> Option<(char, char)> {
+        match self {
+            TextObject::Parentheses => Some(('(', ')')),
+            TextObject::SquareBrackets => Some(('[', ']')),
+            TextObject::CurlyBrackets => Some(('{', '}')),
+            TextObject::AngleBrackets => Some(('<', '>')),
+            TextObject::Word
+            | TextObject::BigWord
+            | TextObject::Sentence
+            | TextObject::Paragraph
+            | TextObject::Quotes
+            | TextObject::BackQuotes
+            | TextObject::DoubleQuotes
+            | TextObject::Tag => None,
+        }
+    }
+
+    /// Computes the range this object covers, around (`around = true`, including the
+    /// delimiters/whitespace) or inside (`around = false`) `relative_to`. Returns `None` if no
+    /// match could be found (e.g. an unbalanced or missing delimiter, or an unmatched tag).
+    pub fn expand_selection(
+        self,
+        map: &DisplaySnapshot,
+        relative_to: DisplayPoint,
+        around: bool,
+    ) -> Option<Range<DisplayPoint>> {
+        match self {
+            TextObject::Word => Some(surrounding_word(map, relative_to, around, false)),
+            TextObject::BigWord => Some(surrounding_word(map, relative_to, around, true)),
+            TextObject::Sentence => Some(surrounding_sentence(map, relative_to, around)),
+            TextObject::Paragraph => Some(surrounding_paragraph(map, relative_to, around)),
+            TextObject::Tag => surrounding_tag(map, relative_to, around),
+            TextObject::Quotes => surrounding_quote(map, relative_to, '\'', around),
+            TextObject::BackQuotes => surrounding_quote(map, relative_to, '`', around),
+            TextObject::DoubleQuotes => surrounding_quote(map, relative_to, '"', around),
+            _ => {
+                let (open, close) = self.delimiters()?;
+                surrounding_pair(map, relative_to, open, close, around)
+            }
+        }
+    }
+}
+
+fn surrounding_word(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    around: bool,
+    ignore_punctuation: bool,
+) -> Range<DisplayPoint> {
+    let classifier = map
+        .buffer_snapshot
+        .char_classifier_at(relative_to.to_point(map))
+        .ignore_punctuation(ignore_punctuation);
+
+    let mut start = movement::find_preceding_boundary_in_line(map, relative_to, |left, right| {
+        classifier.kind(left) != classifier.kind(right)
+    });
+    let mut end = movement::find_boundary_in_line(map, relative_to, |left, right| {
+        classifier.kind(left) != classifier.kind(right)
+    });
+
+    if around {
+        let before_whitespace_end = end;
+        end = movement::find_boundary_in_line(map, end, |left, right| {
+            let _ = left;
+            classifier.kind(right) != CharKind::Whitespace
+        });
+        if end == before_whitespace_end {
+            start = movement::find_preceding_boundary_in_line(map, start, |left, right| {
+                let _ = right;
+                classifier.kind(left) != CharKind::Whitespace
+            });
+        }
+    }
+
+    start..end
+}
+
+fn surrounding_sentence(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    around: bool,
+) -> Range<DisplayPoint> {
+    let start = movement::find_preceding_boundary_in_line(map, relative_to, |left, right| {
+        let _ = right;
+        matches!(left, '.' | '!' | '?')
+    });
+    let mut end = movement::find_boundary_in_line(map, relative_to, |left, right| {
+        let _ = right;
+        matches!(left, '.' | '!' | '?')
+    });
+
+    if around {
+        // Swallow the whitespace separating this sentence from the next one.
+        end = movement::find_boundary_in_line(map, end, |left, right| {
+            let _ = left;
+            !right.is_whitespace()
+        });
+    }
+
+    start..end
+}
+
+fn surrounding_paragraph(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    around: bool,
+) -> Range<DisplayPoint> {
+    let mut start_row = relative_to.row();
+    while start_row > 0 && !map.is_line_blank(start_row - 1) {
+        start_row -= 1;
+    }
+    let mut end_row = relative_to.row();
+    while end_row < map.max_point().row() && !map.is_line_blank(end_row + 1) {
+        end_row += 1;
+    }
+
+    if around {
+        // Swallow the following blank line(s) separating this paragraph from the next one.
+        while end_row < map.max_point().row() && map.is_line_blank(end_row + 1) {
+            end_row += 1;
+        }
+    }
+
+    let start = map.prev_line_boundary(DisplayPoint::new(start_row, 0).to_point(map)).1;
+    let end = map.next_line_boundary(DisplayPoint::new(end_row, 0).to_point(map)).1;
+    start..end
+}
+
+/// Scans outward from `relative_to` in both directions, balancing nesting, to find the
+/// innermost `open`/`close` pair that contains it.
+fn surrounding_pair(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    open: char,
+    close: char,
+    around: bool,
+) -> Option<Range<DisplayPoint>> {
+    let mut depth = 0;
+    let mut start = None;
+    for (point, ch) in movement::chars_before(map, relative_to) {
+        if ch == close && point != relative_to {
+            depth += 1;
+        } else if ch == open {
+            if depth == 0 {
+                start = Some(point);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let open_point = start?;
+
+    let mut depth = 0;
+    let mut end = None;
+    for (point, ch) in movement::chars_after(map, relative_to) {
+        if ch == open && point != relative_to {
+            depth += 1;
+        } else if ch == close {
+            if depth == 0 {
+                end = Some(point);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let close_point = end?;
+
+    if around {
+        Some(open_point..movement::right(map, close_point))
+    } else {
+        Some(movement::right(map, open_point)..close_point)
+    }
+}
+
+/// Finds the nearest pair of `quote` characters (the same character on both sides, e.g. `'`,
+/// `` ` ``, `"`) surrounding `relative_to` on the current line. `surrounding_pair`'s
+/// balance-nesting scan doesn't work here: with `open == close`, every quote looks like a
+/// "close" before it can ever be read as an "open", so it never finds a start. Quotes also don't
+/// nest in vim, so there's nothing to balance anyway — the first quote before the cursor just
+/// pairs with the first one after it, both restricted to the current line.
+fn surrounding_quote(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    quote: char,
+    around: bool,
+) -> Option<Range<DisplayPoint>> {
+    let start = movement::chars_before(map, relative_to)
+        .take_while(|(point, _)| point.row() == relative_to.row())
+        .find(|(_, ch)| *ch == quote)
+        .map(|(point, _)| point)?;
+    let end = movement::chars_after(map, relative_to)
+        .take_while(|(point, _)| point.row() == relative_to.row())
+        .find(|(_, ch)| *ch == quote)
+        .map(|(point, _)| point)?;
+
+    if around {
+        Some(start..movement::right(map, end))
+    } else {
+        Some(movement::right(map, start)..end)
+    }
+}
+
+enum TagToken {
+    Open(String),
+    Close(String),
+}
+
+/// Parses the token that follows a `<` already consumed from `chars`: an opening `<name ...>`,
+/// a closing `</name>`, or a self-closing `<name .../>` (reported as `None`, since it can never
+/// contain `relative_to`). Returns the token together with the position just past the tag's `>`.
+fn parse_tag_token(
+    map: &DisplaySnapshot,
+    chars: &mut std::iter::Peekable<impl Iterator<Item = (DisplayPoint, char)>>,
+) -> Option<(Option<TagToken>, DisplayPoint)> {
+    let closing = matches!(chars.peek(), Some((_, '/')));
+    if closing {
+        chars.next();
+    }
+
+    let mut name = String::new();
+    while let Some(&(_, ch)) = chars.peek() {
+        if ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == ':' {
+            name.push(ch);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut self_closing = false;
+    let gt = loop {
+        match chars.next() {
+            Some((point, '>')) => break point,
+            Some((_, '/')) => self_closing = true,
+            Some(_) => {}
+            None => return None,
+        }
+    };
+    let after = movement::right(map, gt);
+
+    if self_closing {
+        Some((None, after))
+    } else if closing {
+        Some((Some(TagToken::Close(name)), after))
+    } else {
+        Some((Some(TagToken::Open(name)), after))
+    }
+}
+
+/// Finds the innermost `<name>...</name>` pair enclosing `relative_to`, matching tags by name
+/// (not just raw `<`/`>` balance, which falls apart as soon as an opening tag's own `>` is
+/// mistaken for a closing delimiter). Scans backward for the enclosing open tag, balancing any
+/// same-named `<name>...</name>` pairs passed over along the way, then scans forward from there
+/// for the matching close tag, balancing any nested tags of that same name.
+fn surrounding_tag(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    around: bool,
+) -> Option<Range<DisplayPoint>> {
+    let mut depth: HashMap<String, i32> = HashMap::default();
+    let mut backward = movement::chars_before(map, relative_to).peekable();
+    let (name, open_start, open_end) = loop {
+        let (point, ch) = backward.next()?;
+        if ch != '<' {
+            continue;
+        }
+        // `backward` iterates right-to-left, but the rest of this tag reads left-to-right, so
+        // parse it with a forward iterator starting just past the `<`.
+        let mut forward = movement::chars_after(map, movement::right(map, point)).peekable();
+        match parse_tag_token(map, &mut forward) {
+            Some((Some(TagToken::Close(name)), _)) => {
+                *depth.entry(name).or_insert(0) += 1;
+            }
+            Some((Some(TagToken::Open(name)), end)) => {
+                let level = depth.entry(name.clone()).or_insert(0);
+                if *level == 0 {
+                    break (name, point, end);
+                }
+                *level -= 1;
+            }
+            _ => {}
+        }
+    };
+
+    let mut nested = 0;
+    let mut forward = movement::chars_after(map, open_end).peekable();
+    let (close_start, close_end) = loop {
+        let (point, ch) = forward.next()?;
+        if ch != '<' {
+            continue;
+        }
+        match parse_tag_token(map, &mut forward) {
+            Some((Some(TagToken::Open(n)), _)) if n == name => nested += 1,
+            Some((Some(TagToken::Close(n)), end)) if n == name => {
+                if nested == 0 {
+                    break (point, end);
+                }
+                nested -= 1;
+            }
+            _ => {}
+        }
+    };
+
+    if around {
+        Some(open_start..close_end)
+    } else {
+        Some(open_end..close_start)
+    }
+}
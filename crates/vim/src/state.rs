@@ -0,0 +1,38 @@
+/// The current vim mode. `Insert` reuses the host editor's own typing behavior; the rest change
+/// how motions and operators build and act on selections.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+    VisualBlock,
+}
+
+/// The contents of a single vim register (`"`, `0`-`9`, `a`-`z`). `is_line` marks a linewise
+/// yank/delete (`yy`, `dd`), which a subsequent `p`/`P` pastes as whole lines rather than inline
+/// text.
+#[derive(Clone, Debug, Default)]
+pub struct Register {
+    pub text: String,
+    pub is_line: bool,
+}
+
+/// An operator armed by `d`/`c` in Normal mode, waiting on the motion or text object that tells it
+/// what range to act on (`dap`, `ci(`, ...).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Delete,
+    Change,
+}
+
+/// The part of `Vim`'s state that's meaningful to query from outside the crate (the mode) or from
+/// sibling modules assembling a command (the pending operator/count). Register storage lives
+/// directly on `Vim` since it's read and written through dedicated methods rather than matched on.
+#[derive(Default)]
+pub struct VimState {
+    pub mode: Mode,
+    pub pending_operator: Option<Operator>,
+    pub pending_count: Option<usize>,
+}
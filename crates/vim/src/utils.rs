@@ -0,0 +1,33 @@
+use editor::Editor;
+use gpui::ViewContext;
+
+use crate::{state::Register, Vim};
+
+/// Copies the text under the current selections into the resolved register: an explicit `"x`
+/// prefix if one is pending (`register`), or the unnamed register otherwise. `is_line` marks a
+/// linewise yank/delete (`yy`/`dd` vs. a char-wise `v...y`/`v...d`), and `is_yank` distinguishes a
+/// yank from a delete/change so `Vim::write_register` can apply the right register-0/numbered-
+/// register side effects.
+pub fn copy_selections_content(
+    editor: &mut Editor,
+    is_line: bool,
+    is_yank: bool,
+    register: Option<char>,
+    cx: &mut ViewContext<Editor>,
+) {
+    let buffer = editor.buffer().read(cx).snapshot(cx);
+    let text = editor
+        .selections
+        .all::<editor::Point>(cx)
+        .into_iter()
+        .map(|selection| {
+            buffer
+                .text_for_range(selection.start..selection.end)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let contents = Register { text, is_line };
+    cx.global_mut::<Vim>().write_register(register, contents, is_yank);
+}
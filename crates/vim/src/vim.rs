@@ -0,0 +1,145 @@
+mod motion;
+mod normal;
+mod object;
+mod state;
+mod utils;
+mod visual;
+
+use collections::HashMap;
+use editor::{DisplayPoint, Editor};
+use gpui::{MutableAppContext, ViewContext, ViewHandle, WeakViewHandle};
+use workspace::{ItemHandle, Workspace};
+
+pub use state::{Mode, Operator, Register};
+
+/// Global vim state: the current mode, any pending count/register, the register contents, and
+/// (for block visual mode) the anchor/head of the rectangle being built and the rows a
+/// block-insert (`I`/`A`) is in progress on.
+#[derive(Default)]
+pub struct Vim {
+    workspace: Option<WeakViewHandle<Workspace>>,
+    state: state::VimState,
+    registers: HashMap<char, Register>,
+    pub selected_register: Option<char>,
+    pub visual_block_anchor: Option<DisplayPoint>,
+    pub visual_block_head: Option<DisplayPoint>,
+    pub block_insert_rows: Vec<DisplayPoint>,
+}
+
+impl Vim {
+    pub fn read(cx: &MutableAppContext) -> &Self {
+        cx.default_global::<Self>()
+    }
+
+    pub fn update<F, T>(cx: &mut MutableAppContext, update: F) -> T
+    where
+        F: FnOnce(&mut Self, &mut MutableAppContext) -> T,
+    {
+        cx.update_default_global(update)
+    }
+
+    pub fn state(&self) -> &state::VimState {
+        &self.state
+    }
+
+    pub fn switch_mode(&mut self, mode: Mode, cx: &mut MutableAppContext) {
+        let was_block_insert =
+            self.state.mode == Mode::Insert && mode != Mode::Insert && self.block_insert_rows.len() > 1;
+        self.state.mode = mode;
+
+        if was_block_insert {
+            let rows = std::mem::take(&mut self.block_insert_rows);
+            self.update_active_editor(cx, |editor, cx| {
+                visual::replay_block_insert_from_primary(editor, rows, cx);
+            });
+        } else if mode != Mode::Insert {
+            self.block_insert_rows.clear();
+        }
+    }
+
+    pub fn take_count(&mut self) -> Option<usize> {
+        self.state.pending_count.take()
+    }
+
+    /// Accumulates a digit keystroke into the pending count (e.g. `2` then `3` for `23j`).
+    pub fn push_count_digit(&mut self, digit: u8) {
+        let pending = self.state.pending_count.unwrap_or(0);
+        self.state.pending_count = Some(pending * 10 + digit as usize);
+    }
+
+    pub fn pending_operator(&self) -> Option<Operator> {
+        self.state.pending_operator
+    }
+
+    pub fn set_pending_operator(&mut self, operator: Option<Operator>) {
+        self.state.pending_operator = operator;
+    }
+
+    /// Resolves which register a yank/delete/paste should use: an explicit `"x` prefix if one is
+    /// pending (`name`), or the unnamed register otherwise. Writes go through
+    /// `write_register`, which mirrors vim's usual side effects: the unnamed register always ends
+    /// up holding the most recent yank/delete/change regardless of which register was targeted, a
+    /// yank additionally lands in register `0`, and a linewise delete of the unnamed register
+    /// rotates `1`-`9` to make room for it in `1`. An explicit named register (`"ay`) only ever
+    /// writes `a` plus the unnamed mirror — it never touches the numbered registers.
+    pub fn write_register(&mut self, name: Option<char>, contents: Register, is_yank: bool) {
+        let name = name.unwrap_or('"');
+        let explicit = name != '"';
+
+        if explicit {
+            self.registers.insert('"', contents.clone());
+        }
+
+        if !explicit {
+            if is_yank {
+                self.registers.insert('0', contents.clone());
+            } else if contents.is_line {
+                for slot in (b'2'..=b'9').rev() {
+                    let from = (slot - 1) as char;
+                    let to = slot as char;
+                    match self.registers.remove(&from) {
+                        Some(previous) => {
+                            self.registers.insert(to, previous);
+                        }
+                        None => {
+                            self.registers.remove(&to);
+                        }
+                    }
+                }
+                self.registers.insert('1', contents.clone());
+            }
+        }
+
+        self.registers.insert(name, contents);
+    }
+
+    pub fn register(&self, name: char) -> Option<&Register> {
+        self.registers.get(&name)
+    }
+
+    pub fn update_active_editor<F, T>(
+        &mut self,
+        cx: &mut MutableAppContext,
+        update: F,
+    ) -> Option<T>
+    where
+        F: FnOnce(&mut Editor, &mut ViewContext<Editor>) -> T,
+    {
+        let workspace = self.workspace.as_ref()?.upgrade(cx)?;
+        let editor: ViewHandle<Editor> = workspace.read(cx).active_item(cx)?.act_as::<Editor>(cx)?;
+        Some(editor.update(cx, update))
+    }
+}
+
+pub fn init(cx: &mut MutableAppContext) {
+    cx.set_default_global(Vim::default());
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        let workspace_handle = cx.weak_handle();
+        Vim::update(cx, |vim, _| vim.workspace = Some(workspace_handle));
+    })
+    .detach();
+
+    motion::init(cx);
+    normal::init(cx);
+    visual::init(cx);
+}
@@ -1,17 +1,23 @@
 use collections::HashMap;
-use editor::{Autoscroll, Bias};
+use editor::{Autoscroll, Bias, SelectionGoal};
 use gpui::{actions, MutableAppContext, ViewContext};
 use workspace::Workspace;
 
-use crate::{motion::Motion, state::Mode, utils::copy_selections_content, Vim};
+use crate::{motion::Motion, object::TextObject, state::Mode, utils::copy_selections_content, Vim};
 
 actions!(
     vim,
     [
         VisualDelete,
         VisualChange,
+        VisualYank,
         VisualLineDelete,
-        VisualLineChange
+        VisualLineChange,
+        VisualLineYank,
+        VisualBlockDelete,
+        VisualBlockChange,
+        VisualBlockInsert,
+        VisualBlockAppend
     ]
 );
 
@@ -20,14 +26,56 @@ pub fn init(cx: &mut MutableAppContext) {
     cx.add_action(change_line);
     cx.add_action(delete);
     cx.add_action(delete_line);
+    cx.add_action(yank);
+    cx.add_action(yank_line);
+    cx.add_action(block_delete);
+    cx.add_action(block_change);
+    cx.add_action(block_insert);
+    cx.add_action(block_append);
 }
 
-pub fn visual_motion(motion: Motion, cx: &mut MutableAppContext) {
+pub fn visual_motion(motion: Motion, times: usize, cx: &mut MutableAppContext) {
     Vim::update(cx, |vim, cx| {
+        let is_block = vim.state().mode == Mode::VisualBlock;
+
+        if is_block {
+            // Block selections are a rectangle spanned by a fixed anchor corner and a cursor
+            // corner that moves with each motion. We track that pair explicitly on `Vim` rather
+            // than re-deriving it from the rendered per-row selections: after the first motion
+            // there are N of those (one per row, each already clipped to its own length), and
+            // reapplying the motion to every one of them independently, then rebuilding the
+            // rectangle from their drifted heads/tails, compounds error on every subsequent
+            // keystroke (most visibly for horizontal motions, which widen the block on every
+            // move instead of by exactly one column).
+            let mut anchor = vim.visual_block_anchor;
+            let mut head = vim.visual_block_head;
+            vim.update_active_editor(cx, |editor, cx| {
+                let map = editor.selections.display_map(cx);
+                let anchor = *anchor.get_or_insert_with(|| {
+                    editor
+                        .selections
+                        .all::<editor::DisplayPoint>(cx)
+                        .first()
+                        .map(|s| s.tail())
+                        .expect("visual block mode always has an active selection")
+                });
+                let current_head = head.unwrap_or(anchor);
+                let (new_head, _) =
+                    motion.move_point(&map, current_head, SelectionGoal::None, times);
+                let new_head = map.clip_at_line_end(new_head);
+                head = Some(new_head);
+                build_block_selections(editor, anchor, new_head, cx);
+            });
+            vim.visual_block_anchor = anchor;
+            vim.visual_block_head = head;
+            return;
+        }
+
         vim.update_active_editor(cx, |editor, cx| {
             editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
                 s.move_with(|map, selection| {
-                    let (new_head, goal) = motion.move_point(map, selection.head(), selection.goal);
+                    let (new_head, goal) =
+                        motion.move_point(map, selection.head(), selection.goal, times);
                     let new_head = map.clip_at_line_end(new_head);
                     let was_reversed = selection.reversed;
                     selection.set_head(new_head, goal);
@@ -49,8 +97,82 @@ pub fn visual_motion(motion: Motion, cx: &mut MutableAppContext) {
     });
 }
 
+/// Rebuilds the per-row selections that make up a block (Ctrl-V) visual selection from the
+/// rectangle spanned by `anchor` (the fixed corner) and `head` (the corner that moves with each
+/// motion), clipping each row to its own length. Always takes the true anchor/head pair rather
+/// than re-deriving bounds from the previous call's rendered selections.
+fn build_block_selections(
+    editor: &mut editor::Editor,
+    anchor: editor::DisplayPoint,
+    head: editor::DisplayPoint,
+    cx: &mut ViewContext<editor::Editor>,
+) {
+    let map = editor.selections.display_map(cx);
+    let start_row = anchor.row().min(head.row());
+    let end_row = anchor.row().max(head.row());
+    let left_column = anchor.column().min(head.column());
+    let right_column = anchor.column().max(head.column());
+
+    let mut ranges = Vec::new();
+    for row in start_row..=end_row {
+        let left = map.clip_point(editor::DisplayPoint::new(row, left_column), Bias::Left);
+        // Include the character under the head, matching the other visual modes' convention
+        // of treating the selection as an inclusive range.
+        let right = map.clip_point(editor::DisplayPoint::new(row, right_column + 1), Bias::Right);
+        ranges.push(left..right);
+    }
+
+    editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+        s.select_display_ranges(ranges);
+    });
+}
+
+/// Replaces the current visual (visual-line or visual-block) selection with a text object, e.g.
+/// `viw` or `va(`. In operator-pending mode (`dap`, `ci(`, ...) `normal::object` computes the
+/// object's range itself and doesn't go through here.
+pub fn visual_object(object: TextObject, around: bool, cx: &mut MutableAppContext) {
+    Vim::update(cx, |vim, cx| {
+        expand_selection_to_object(vim, cx, object, around);
+    });
+}
+
+/// Replaces the current selection's head/tail with a text object's range. Shared by
+/// `visual_object` and `normal::object` (when it's called in a visual mode), which both already
+/// hold `&mut Vim`/`cx` from their own `Vim::update` call and so can't route through
+/// `visual_object` itself without re-entering it.
+pub(crate) fn expand_selection_to_object(
+    vim: &mut Vim,
+    cx: &mut MutableAppContext,
+    object: TextObject,
+    around: bool,
+) {
+    vim.update_active_editor(cx, |editor, cx| {
+        editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+            s.move_with(|map, selection| {
+                if let Some(range) = object.expand_selection(map, selection.head(), around) {
+                    selection.start = range.start;
+                    selection.end = range.end;
+                    // The object's end is exclusive, so keep the head at the start: the visual
+                    // operators only extend the end by one for non-reversed selections.
+                    selection.reversed = true;
+                }
+            });
+        });
+    });
+}
+
+/// A count given right before a char-wise or block visual operator (e.g. `v2c`, `ctrl-v 2 x`) has
+/// nothing left to multiply: unlike `c2w`, where the count belongs to the motion that built the
+/// selection, here the selection is already whatever was on screen when the operator was pressed.
+/// Consume it so it doesn't leak into the next command, rather than silently ignoring it forever.
+fn ignore_pending_count(vim: &mut Vim) {
+    vim.take_count();
+}
+
 pub fn change(_: &mut Workspace, _: &VisualChange, cx: &mut ViewContext<Workspace>) {
     Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        ignore_pending_count(vim);
         vim.update_active_editor(cx, |editor, cx| {
             editor.set_clip_at_line_ends(false, cx);
             editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
@@ -63,7 +185,7 @@ pub fn change(_: &mut Workspace, _: &VisualChange, cx: &mut ViewContext<Workspac
                     }
                 });
             });
-            copy_selections_content(editor, false, cx);
+            copy_selections_content(editor, false, false, register, cx);
             editor.insert("", cx);
         });
         vim.switch_mode(Mode::Insert, cx);
@@ -72,15 +194,21 @@ pub fn change(_: &mut Workspace, _: &VisualChange, cx: &mut ViewContext<Workspac
 
 pub fn change_line(_: &mut Workspace, _: &VisualLineChange, cx: &mut ViewContext<Workspace>) {
     Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        let times = vim.take_count().unwrap_or(1);
         vim.update_active_editor(cx, |editor, cx| {
             editor.set_clip_at_line_ends(false, cx);
             editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
                 s.move_with(|map, selection| {
+                    // A count before the operator (e.g. `2cc`) extends the selection that many
+                    // extra lines beyond what was already selected.
+                    *selection.end.row_mut() =
+                        (selection.end.row() + times as u32 - 1).min(map.max_point().row());
                     selection.start = map.prev_line_boundary(selection.start.to_point(map)).1;
                     selection.end = map.next_line_boundary(selection.end.to_point(map)).1;
                 });
             });
-            copy_selections_content(editor, true, cx);
+            copy_selections_content(editor, true, false, register, cx);
             editor.insert("", cx);
         });
         vim.switch_mode(Mode::Insert, cx);
@@ -89,6 +217,8 @@ pub fn change_line(_: &mut Workspace, _: &VisualLineChange, cx: &mut ViewContext
 
 pub fn delete(_: &mut Workspace, _: &VisualDelete, cx: &mut ViewContext<Workspace>) {
     Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        ignore_pending_count(vim);
         vim.update_active_editor(cx, |editor, cx| {
             editor.set_clip_at_line_ends(false, cx);
             editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
@@ -101,7 +231,7 @@ pub fn delete(_: &mut Workspace, _: &VisualDelete, cx: &mut ViewContext<Workspac
                     }
                 });
             });
-            copy_selections_content(editor, false, cx);
+            copy_selections_content(editor, false, false, register, cx);
             editor.insert("", cx);
 
             // Fixup cursor position after the deletion
@@ -120,12 +250,18 @@ pub fn delete(_: &mut Workspace, _: &VisualDelete, cx: &mut ViewContext<Workspac
 
 pub fn delete_line(_: &mut Workspace, _: &VisualLineDelete, cx: &mut ViewContext<Workspace>) {
     Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        let times = vim.take_count().unwrap_or(1);
         vim.update_active_editor(cx, |editor, cx| {
             editor.set_clip_at_line_ends(false, cx);
             let mut original_columns: HashMap<_, _> = Default::default();
             editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
                 s.move_with(|map, selection| {
                     original_columns.insert(selection.id, selection.head().column());
+                    // A count before the operator (e.g. `2D`) extends the selection that many
+                    // extra lines beyond what was already selected.
+                    *selection.end.row_mut() =
+                        (selection.end.row() + times as u32 - 1).min(map.max_point().row());
                     selection.start = map.prev_line_boundary(selection.start.to_point(map)).1;
 
                     if selection.end.row() < map.max_point().row() {
@@ -141,7 +277,7 @@ pub fn delete_line(_: &mut Workspace, _: &VisualLineDelete, cx: &mut ViewContext
                     selection.end = map.next_line_boundary(selection.end.to_point(map)).1;
                 });
             });
-            copy_selections_content(editor, true, cx);
+            copy_selections_content(editor, true, false, register, cx);
             editor.insert("", cx);
 
             // Fixup cursor position after the deletion
@@ -161,6 +297,213 @@ pub fn delete_line(_: &mut Workspace, _: &VisualLineDelete, cx: &mut ViewContext
     });
 }
 
+pub fn yank(_: &mut Workspace, _: &VisualYank, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        ignore_pending_count(vim);
+        vim.update_active_editor(cx, |editor, cx| {
+            editor.set_clip_at_line_ends(false, cx);
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    if !selection.reversed {
+                        // Head is at the end of the selection. Adjust the end position to
+                        // to include the character under the cursor.
+                        *selection.end.column_mut() = selection.end.column() + 1;
+                        selection.end = map.clip_point(selection.end, Bias::Right);
+                    }
+                });
+            });
+            copy_selections_content(editor, false, true, register, cx);
+
+            // Yanking doesn't remove any text, so just collapse each selection back to its start.
+            editor.set_clip_at_line_ends(true, cx);
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = map.clip_point(selection.start, Bias::Left);
+                    selection.collapse_to(cursor, selection.goal)
+                });
+            });
+        });
+        vim.switch_mode(Mode::Normal, cx);
+    });
+}
+
+pub fn yank_line(_: &mut Workspace, _: &VisualLineYank, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        let times = vim.take_count().unwrap_or(1);
+        vim.update_active_editor(cx, |editor, cx| {
+            editor.set_clip_at_line_ends(false, cx);
+            let mut original_columns: HashMap<_, _> = Default::default();
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    original_columns.insert(selection.id, selection.head().column());
+                    *selection.end.row_mut() =
+                        (selection.end.row() + times as u32 - 1).min(map.max_point().row());
+                    selection.start = map.prev_line_boundary(selection.start.to_point(map)).1;
+                    selection.end = map.next_line_boundary(selection.end.to_point(map)).1;
+                });
+            });
+            copy_selections_content(editor, true, true, register, cx);
+
+            // Yanking doesn't remove any text, so just collapse the selections back to their
+            // original line.
+            editor.set_clip_at_line_ends(true, cx);
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    let mut cursor = selection.start;
+                    if let Some(column) = original_columns.get(&selection.id) {
+                        *cursor.column_mut() = *column
+                    }
+                    cursor = map.clip_point(cursor, Bias::Left);
+                    selection.collapse_to(cursor, selection.goal)
+                });
+            });
+        });
+        vim.switch_mode(Mode::Normal, cx);
+    });
+}
+
+pub fn block_delete(_: &mut Workspace, _: &VisualBlockDelete, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        ignore_pending_count(vim);
+        vim.visual_block_anchor = None;
+        vim.visual_block_head = None;
+        vim.update_active_editor(cx, |editor, cx| {
+            // Each selection already spans exactly one row of the block, clipped to that row's
+            // length and inclusive of the right edge, so there's no extra adjustment needed
+            // before deleting.
+            editor.set_clip_at_line_ends(false, cx);
+            copy_selections_content(editor, false, false, register, cx);
+            editor.insert("", cx);
+
+            editor.set_clip_at_line_ends(true, cx);
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = map.clip_point(selection.head(), Bias::Left);
+                    selection.collapse_to(cursor, selection.goal)
+                });
+            });
+        });
+        vim.switch_mode(Mode::Normal, cx);
+    });
+}
+
+pub fn block_change(_: &mut Workspace, _: &VisualBlockChange, cx: &mut ViewContext<Workspace>) {
+    Vim::update(cx, |vim, cx| {
+        let register = vim.selected_register.take();
+        ignore_pending_count(vim);
+        vim.visual_block_anchor = None;
+        vim.visual_block_head = None;
+        vim.update_active_editor(cx, |editor, cx| {
+            editor.set_clip_at_line_ends(false, cx);
+            copy_selections_content(editor, false, false, register, cx);
+            editor.insert("", cx);
+        });
+        vim.switch_mode(Mode::Insert, cx);
+    });
+}
+
+pub fn block_insert(_: &mut Workspace, _: &VisualBlockInsert, cx: &mut ViewContext<Workspace>) {
+    enter_block_insert(cx, BlockInsertEdge::Left);
+}
+
+pub fn block_append(_: &mut Workspace, _: &VisualBlockAppend, cx: &mut ViewContext<Workspace>) {
+    enter_block_insert(cx, BlockInsertEdge::Right);
+}
+
+/// Which edge of the block each row's cursor is placed on before entering insert mode.
+enum BlockInsertEdge {
+    Left,
+    Right,
+}
+
+/// Collapses the block to a single cursor on every row's left or right edge, remembers those
+/// rows on `Vim` so the typed text can be replayed to the rest of them, and enters insert mode.
+///
+/// Rows that don't actually reach the block's column (a ragged selection, where some line is
+/// shorter than the rest of the block) are left out entirely rather than getting a cursor at
+/// their own clipped end, matching vim's behavior of skipping those rows for `I`/`A`.
+fn enter_block_insert(cx: &mut ViewContext<Workspace>, edge: BlockInsertEdge) {
+    Vim::update(cx, |vim, cx| {
+        let anchor = vim.visual_block_anchor;
+        let head = vim.visual_block_head;
+        let rows = vim.update_active_editor(cx, |editor, cx| {
+            let map = editor.selections.display_map(cx);
+            let (start_row, end_row, left_column, right_column) = match (anchor, head) {
+                (Some(anchor), Some(head)) => (
+                    anchor.row().min(head.row()),
+                    anchor.row().max(head.row()),
+                    anchor.column().min(head.column()),
+                    anchor.column().max(head.column()),
+                ),
+                _ => return None,
+            };
+            let column = match edge {
+                BlockInsertEdge::Left => left_column,
+                BlockInsertEdge::Right => right_column + 1,
+            };
+
+            let rows = (start_row..=end_row)
+                .filter(|&row| map.line_len(row) >= column)
+                .map(|row| map.clip_point(editor::DisplayPoint::new(row, column), Bias::Left))
+                .collect::<Vec<_>>();
+
+            editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+                s.select_display_ranges(rows.iter().map(|point| *point..*point));
+            });
+
+            Some(rows)
+        });
+        if let Some(rows) = rows.flatten() {
+            vim.block_insert_rows = rows;
+        }
+        vim.visual_block_anchor = None;
+        vim.visual_block_head = None;
+        vim.switch_mode(Mode::Insert, cx);
+    });
+}
+
+/// Replays the text typed during a block-insert (`I`/`A`) to every row of the block. Called when
+/// leaving insert mode after a block-insert was started.
+pub fn replay_block_insert(editor: &mut editor::Editor, rows: Vec<editor::DisplayPoint>, text: &str, cx: &mut ViewContext<editor::Editor>) {
+    if text.is_empty() || rows.len() < 2 {
+        return;
+    }
+    editor.change_selections(Some(Autoscroll::Fit), cx, |s| {
+        s.select_display_ranges(rows[1..].iter().map(|point| *point..*point));
+    });
+    editor.insert(text, cx);
+}
+
+/// Figures out what was typed during a block-insert and replays it via `replay_block_insert`.
+/// `rows[0]` is where typing started; as long as the primary cursor is still on that same row
+/// (typing a newline mid block-insert isn't supported — vim itself also only replays up to the
+/// first line break), whatever sits between the two is exactly what got typed, insertions and
+/// backspaces alike, so there's no need to track keystrokes one at a time.
+pub(crate) fn replay_block_insert_from_primary(
+    editor: &mut editor::Editor,
+    rows: Vec<editor::DisplayPoint>,
+    cx: &mut ViewContext<editor::Editor>,
+) {
+    let Some(start) = rows.first().copied() else {
+        return;
+    };
+    let head = editor.selections.newest::<editor::DisplayPoint>(cx).head();
+    if head.row() != start.row() || head < start {
+        return;
+    }
+
+    let map = editor.selections.display_map(cx);
+    let typed = editor::movement::chars_after(&map, start)
+        .take_while(|(point, _)| *point < head)
+        .map(|(_, ch)| ch)
+        .collect::<String>();
+
+    replay_block_insert(editor, rows, &typed, cx);
+}
+
 #[cfg(test)]
 mod test {
     use indoc::indoc;
@@ -521,4 +864,303 @@ mod test {
                 |"},
         );
     }
+
+    #[gpui::test]
+    async fn test_visual_yank(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "w", "y"]).mode_after(Mode::Normal);
+        cx.assert("The quick |brown", "The |quick brown");
+        cx.simulate_keystroke("p");
+        cx.assert_editor_state("The q|quick brownuick brown");
+
+        let mut cx = cx.binding(["\"", "a", "y", "w"]).mode_after(Mode::Normal);
+        cx.assert("The |quick brown", "The |quick brown");
+        cx.simulate_keystrokes(["$", "\"", "a", "p"]);
+        cx.assert_editor_state("The quick brow|quick nn");
+    }
+
+    #[gpui::test]
+    async fn test_visual_line_yank(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["shift-V", "y"]).mode_after(Mode::Normal);
+        cx.assert(
+            indoc! {"
+                The qu|ick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                The qu|ick brown
+                fox jumps over
+                the lazy dog"},
+        );
+        cx.simulate_keystroke("p");
+        cx.assert_editor_state(indoc! {"
+            The quick brown
+            |The quick brown
+            fox jumps over
+            the lazy dog"});
+    }
+
+    #[gpui::test]
+    async fn test_visual_block_delete(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["ctrl-v", "j", "j", "x"]);
+        cx.assert(
+            indoc! {"
+                The |quick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                The |uick brown
+                fox umps over
+                the azy dog"},
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_block_delete_with_repeated_motion(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        // Two horizontal motions in a row must widen the block by exactly one column each, not
+        // compound drift from re-deriving the rectangle out of the previous motion's per-row
+        // selections.
+        let mut cx = cx.binding(["ctrl-v", "l", "l", "x"]);
+        cx.assert(
+            indoc! {"
+                The |quick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                The |ck brown
+                fox jumps over
+                the lazy dog"},
+        );
+        // A vertical motion followed by a horizontal one must grow the rectangle on both axes
+        // from the same fixed anchor, not from the intermediate per-row selections.
+        let mut cx = cx.binding(["ctrl-v", "j", "l", "x"]);
+        cx.assert(
+            indoc! {"
+                The |quick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                The |ick brown
+                fox mps over
+                the lazy dog"},
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_block_insert(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx
+            .binding(["ctrl-v", "j", "j", "shift-i"])
+            .mode_after(Mode::Insert);
+        cx.assert(
+            indoc! {"
+                |The quick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                |The quick brown
+                fox jumps over
+                the lazy dog"},
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_block_insert_with_ragged_selection(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        // The middle line is too short to reach the block's column, so it must be skipped
+        // entirely rather than getting an insertion point at its own (wrong) clipped end.
+        let mut cx = cx
+            .binding(["ctrl-v", "2", "j", "l", "l", "l", "shift-i", "X", "escape"])
+            .mode_after(Mode::Normal);
+        cx.assert(
+            indoc! {"
+                The |quick brown
+                fo
+                the lazy dog"},
+            indoc! {"
+                The |Xquick brown
+                fo
+                the Xlazy dog"},
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_word_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "w"]).mode_after(Mode::Visual);
+        cx.assert(
+            "The qu|ick brown",
+            "The {quick] brown",
+        );
+        let mut cx = cx.binding(["v", "a", "w"]).mode_after(Mode::Visual);
+        cx.assert(
+            "The qu|ick brown",
+            "The {quick ]brown",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_big_word_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "shift-w"]).mode_after(Mode::Visual);
+        cx.assert(
+            "The qu|ick-brown fox",
+            "The {quick-brown] fox",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_parentheses_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "("]).mode_after(Mode::Visual);
+        cx.assert(
+            "fn f(a, b|, c) {}",
+            "fn f({a, b, c]) {}",
+        );
+        let mut cx = cx.binding(["v", "a", "("]).mode_after(Mode::Visual);
+        cx.assert(
+            "fn f(a, b|, c) {}",
+            "fn f{(a, b, c)] {}",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_square_brackets_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "["]).mode_after(Mode::Visual);
+        cx.assert(
+            "let a = [1, 2|, 3];",
+            "let a = [{1, 2, 3]];",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_curly_brackets_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "{"]).mode_after(Mode::Visual);
+        cx.assert(
+            "if x { y = |1; }",
+            "if x {{ y = 1; ]}",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_angle_brackets_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "<"]).mode_after(Mode::Visual);
+        cx.assert(
+            "Vec<Hash|Map<K, V>>",
+            "Vec<{HashMap<K, V>]>",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_single_quotes_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "'"]).mode_after(Mode::Visual);
+        cx.assert(
+            "let c = 'a|b';",
+            "let c = '{ab]';",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_back_quotes_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "`"]).mode_after(Mode::Visual);
+        cx.assert(
+            "let c = `a|b`;",
+            "let c = `{ab]`;",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_double_quotes_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "\""]).mode_after(Mode::Visual);
+        cx.assert(
+            "let c = \"a|b\";",
+            "let c = \"{ab]\";",
+        );
+        let mut cx = cx.binding(["v", "a", "\""]).mode_after(Mode::Visual);
+        cx.assert(
+            "let c = \"a|b\";",
+            "let c = {\"ab\"];",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_tag_object(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "i", "t"]).mode_after(Mode::Visual);
+        cx.assert(
+            "<div>te|xt</div>",
+            "<div>{text]</div>",
+        );
+        let mut cx = cx.binding(["v", "a", "t"]).mode_after(Mode::Visual);
+        cx.assert(
+            "<div>te|xt</div>",
+            "{<div>text</div>]",
+        );
+        // Nested tags with the same name must not confuse the matcher.
+        let mut cx = cx.binding(["v", "i", "t"]).mode_after(Mode::Visual);
+        cx.assert(
+            "<div><div>inner</div> ou|ter</div>",
+            "<div>{<div>inner</div> outer]</div>",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_normal_delete_inside_parentheses(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["d", "i", "("]).mode_after(Mode::Normal);
+        cx.assert("fn f(a, b|, c) {}", "fn f(|) {}");
+    }
+
+    #[gpui::test]
+    async fn test_normal_change_inside_word(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["c", "i", "w"]).mode_after(Mode::Insert);
+        cx.assert("The qu|ick brown", "The | brown");
+    }
+
+    #[gpui::test]
+    async fn test_visual_motion_with_count(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        let mut cx = cx.binding(["v", "2", "j"]).mode_after(Mode::Visual);
+        cx.assert(
+            indoc! {"
+                The |quick brown
+                fox jumps over
+                the lazy dog"},
+            indoc! {"
+                The [quick brown
+                fox jumps over
+                the }lazy dog"},
+        );
+
+        let mut cx = cx.binding(["shift-V", "2", "d"]).mode_after(Mode::Normal);
+        cx.assert(
+            indoc! {"
+                The qu|ick brown
+                fox jumps over
+                the lazy dog"},
+            "the la|zy dog",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_visual_delete_ignores_trailing_count(cx: &mut gpui::TestAppContext) {
+        let cx = VimTestContext::new(cx, true).await;
+        // Unlike `shift-V 2 d` above, a char-wise visual selection is already fixed by the time
+        // the operator is pressed, so a trailing count has nothing left to multiply and `v l 2
+        // d` must behave exactly like `v l d`.
+        let mut cx = cx.binding(["v", "l", "2", "d"]);
+        cx.assert("The qu|ick brown", "The qu|k brown");
+        let mut cx = cx.binding(["v", "l", "d"]);
+        cx.assert("The qu|ick brown", "The qu|k brown");
+    }
 }